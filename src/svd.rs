@@ -0,0 +1,217 @@
+//! Truncated SVD for turning a sparse `PmiMatrix` into dense embeddings.
+//!
+//! This is a small, self-contained linear-algebra routine (randomized range
+//! finding + a dense Jacobi eigensolver) rather than a general-purpose
+//! library: every helper here exists only to serve `reduce_dims`.
+
+use std::cmp::Ordering;
+use std::f32::EPSILON;
+
+use super::{DenseEmbedding, PmiMatrix, Xorshift64};
+
+// Treats a NaN eigenvalue (only possible if the input matrix itself
+// contained NaN/inf) as the worst possible component rather than letting it
+// propagate into a `partial_cmp` that returns `None`.
+fn sortable(value: f32) -> f32 {
+    if value.is_nan() {
+        f32::NEG_INFINITY
+    } else {
+        value
+    }
+}
+
+fn random_gaussian_matrix(rows: usize, cols: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut rng = Xorshift64::new(seed);
+    (0..rows)
+        .map(|_| (0..cols).map(|_| rng.next_gaussian()).collect())
+        .collect()
+}
+
+fn mat_mul(a: &[Vec<f32>], b: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    let n = a.len();
+    let p = b.len();
+    let q = if p > 0 { b[0].len() } else { 0 };
+    let mut out = vec![vec![0.0; q]; n];
+    for (i, row) in a.iter().enumerate() {
+        for (k, &a_ik) in row.iter().enumerate() {
+            if a_ik == 0.0 {
+                continue;
+            }
+            for j in 0..q {
+                out[i][j] += a_ik * b[k][j];
+            }
+        }
+    }
+    out
+}
+
+fn transpose(m: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    let rows = m.len();
+    let cols = if rows > 0 { m[0].len() } else { 0 };
+    let mut out = vec![vec![0.0; rows]; cols];
+    for (i, row) in m.iter().enumerate() {
+        for (j, &v) in row.iter().enumerate() {
+            out[j][i] = v;
+        }
+    }
+    out
+}
+
+// Modified Gram-Schmidt: returns a matrix whose columns are the
+// orthonormalized columns of `y` (`y` itself is left untouched).
+fn orthonormalize_columns(y: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    let n = y.len();
+    let k = if n > 0 { y[0].len() } else { 0 };
+    let mut cols: Vec<Vec<f32>> = (0..k).map(|j| (0..n).map(|i| y[i][j]).collect()).collect();
+    for j in 0..k {
+        for p in 0..j {
+            let dot: f32 = cols[j].iter().zip(cols[p].iter()).map(|(&a, &b)| a * b).sum();
+            for i in 0..n {
+                cols[j][i] -= dot * cols[p][i];
+            }
+        }
+        let norm = cols[j].iter().map(|&v| v * v).sum::<f32>().sqrt();
+        if norm > EPSILON {
+            for v in &mut cols[j] {
+                *v /= norm;
+            }
+        }
+    }
+    let mut q = vec![vec![0.0; k]; n];
+    for (j, col) in cols.iter().enumerate() {
+        for (i, &v) in col.iter().enumerate() {
+            q[i][j] = v;
+        }
+    }
+    q
+}
+
+// Classical Jacobi eigenvalue algorithm for a small symmetric matrix `a`:
+// each rotation zeroes the largest off-diagonal element, for at most 100
+// rotations. Returns (eigenvalues, eigenvectors-as-columns).
+fn jacobi_eigen(a: &[Vec<f32>]) -> (Vec<f32>, Vec<Vec<f32>>) {
+    let n = a.len();
+    let mut a: Vec<Vec<f32>> = a.to_vec();
+    let mut v = vec![vec![0.0; n]; n];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _ in 0..100 {
+        let mut off = 0.0f32;
+        let (mut p, mut q) = (0, 1);
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if a[i][j].abs() > off {
+                    off = a[i][j].abs();
+                    p = i;
+                    q = j;
+                }
+            }
+        }
+        if off < 1e-6 {
+            break;
+        }
+
+        let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+        let t = if theta == 0.0 {
+            1.0
+        } else {
+            theta.signum() / (theta.abs() + (1.0 + theta * theta).sqrt())
+        };
+        let c = 1.0 / (1.0 + t * t).sqrt();
+        let s = t * c;
+
+        for i in 0..n {
+            let (aip, aiq) = (a[i][p], a[i][q]);
+            a[i][p] = c * aip - s * aiq;
+            a[i][q] = s * aip + c * aiq;
+        }
+        for i in 0..n {
+            let (api, aqi) = (a[p][i], a[q][i]);
+            a[p][i] = c * api - s * aqi;
+            a[q][i] = s * api + c * aqi;
+        }
+        for i in 0..n {
+            let (vip, viq) = (v[i][p], v[i][q]);
+            v[i][p] = c * vip - s * viq;
+            v[i][q] = s * vip + c * viq;
+        }
+    }
+
+    let eigenvalues = (0..n).map(|i| a[i][i]).collect();
+    (eigenvalues, v)
+}
+
+// Truncated SVD via randomized range finding (Halko et al.): project the
+// PPMI matrix onto a small random subspace, orthonormalize that subspace,
+// then do a cheap dense SVD there and lift the result back to full size.
+pub fn reduce_dims(m: &PmiMatrix, dim: usize) -> DenseEmbedding {
+    let n = m.0.len();
+    if n == 0 || dim == 0 {
+        return DenseEmbedding(vec![Vec::new(); n]);
+    }
+
+    let oversample = 5;
+    let k = (dim + oversample).min(n);
+
+    let omega = random_gaussian_matrix(n, k, 0x5eed_c0de_1234_5678);
+    let y = mat_mul(&m.0, &omega);
+    let q = orthonormalize_columns(&y);
+
+    let qt = transpose(&q);
+    let b = mat_mul(&qt, &m.0); // k x n
+    let bt = transpose(&b);
+    let bbt = mat_mul(&b, &bt); // k x k, symmetric
+
+    let (eigenvalues, eigenvectors) = jacobi_eigen(&bbt);
+    let mut order: Vec<usize> = (0..k).collect();
+    // Descending by eigenvalue (NaN sorts last), then by index so ties are
+    // deterministic instead of depending on the sort's internal ordering.
+    order.sort_by(|&i, &j| {
+        sortable(eigenvalues[j])
+            .partial_cmp(&sortable(eigenvalues[i]))
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| i.cmp(&j))
+    });
+    let dim = dim.min(k);
+    let sigma: Vec<f32> = order[..dim]
+        .iter()
+        .map(|&i| eigenvalues[i].max(0.0).sqrt())
+        .collect();
+    let ub_dim: Vec<Vec<f32>> = (0..k)
+        .map(|row| order[..dim].iter().map(|&col| eigenvectors[row][col]).collect())
+        .collect();
+
+    let u_full = mat_mul(&q, &ub_dim); // n x dim
+    DenseEmbedding(
+        u_full
+            .into_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(sigma.iter())
+                    .map(|(&u, &s)| u * s)
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_dims_handles_nan_input_without_panicking() {
+        let m = PmiMatrix(vec![
+            vec![1.0, 2.0, f32::NAN, 0.5],
+            vec![2.0, 1.0, 0.5, 0.25],
+            vec![f32::NAN, 0.5, 1.0, 0.75],
+            vec![0.5, 0.25, 0.75, 1.0],
+        ]);
+
+        let dense = reduce_dims(&m, 2);
+        assert_eq!(dense.0.len(), 4);
+        assert!(dense.0.iter().all(|v| v.len() == 2));
+    }
+}
@@ -1,5 +1,13 @@
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::convert::TryInto;
 use std::f32::EPSILON;
+use std::fmt;
+
+mod svd;
+pub use svd::reduce_dims;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct WordId(pub usize);
@@ -62,35 +70,141 @@ pub fn cos_similarity(x: &[usize], y: &[usize]) -> f32 {
         .sum()
 }
 
+// How many typos a query word is allowed to have before a vocabulary word
+// is no longer considered a candidate derivation: short words tolerate less
+// absolute edit distance than long ones before they stop meaning the same
+// thing (mirrors MeiliSearch's length-scaled typo tolerance).
+fn max_typos(word: &str) -> usize {
+    if word.chars().count() <= 5 {
+        1
+    } else {
+        2
+    }
+}
+
+// Bounded Levenshtein distance between `a` and `b`, or `None` once it is
+// certain the distance exceeds `max_distance`. Reuses `row` across calls so
+// scanning the whole vocabulary doesn't allocate a DP row per word.
+fn bounded_edit_distance(a: &str, b: &str, max_distance: usize, row: &mut Vec<usize>) -> Option<usize> {
+    let b_chars = b.chars().collect::<Vec<_>>();
+    row.clear();
+    row.extend(0..=b_chars.len());
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i + 1;
+        let mut row_min = row[0];
+        for (j, &cb) in b_chars.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            let deletion = row[j + 1] + 1;
+            let insertion = row[j] + 1;
+            let substitution = prev_diag + cost;
+            prev_diag = row[j + 1];
+            row[j + 1] = deletion.min(insertion).min(substitution);
+            row_min = row_min.min(row[j + 1]);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+    }
+
+    let distance = row[b_chars.len()];
+    (distance <= max_distance).then_some(distance)
+}
+
+// Finds vocabulary words within `query`'s typo budget, sorted by edit
+// distance and then alphabetically so the closest derivation is first.
+pub fn find_word_derivations<'a>(query: &str, word_to_id: &'a WordToId) -> Vec<(&'a str, usize)> {
+    let max_distance = max_typos(query);
+    let mut row = Vec::with_capacity(query.chars().count() + 1);
+    let mut derivations = word_to_id
+        .0
+        .keys()
+        .filter_map(|word| {
+            bounded_edit_distance(query, word, max_distance, &mut row).map(|d| (word.as_str(), d))
+        })
+        .collect::<Vec<_>>();
+    derivations.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+    derivations
+}
+
+// Wraps a scored word so it can live in a `BinaryHeap`: ordered by score
+// first (NaN is never produced, so `unwrap_or(Equal)` is just a safety net),
+// then by word so ties break deterministically instead of by insertion order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScoredWord<'a> {
+    score: f32,
+    word: &'a str,
+}
+
+impl<'a> Eq for ScoredWord<'a> {}
+
+impl<'a> PartialOrd for ScoredWord<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for ScoredWord<'a> {
+    // Ascending by score, then *descending* by word so that, once wrapped in
+    // `Reverse` for the min-heap below, the word sorted first alphabetically
+    // is the one kept on a tie rather than the one evicted.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .partial_cmp(&other.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.word.cmp(self.word))
+    }
+}
+
 pub fn most_similar<'a>(
     query: &str,
     word_to_id: &WordToId,
     id_to_word: &'a IdToWord,
     word_matrix: &CoMatrix,
+    limit: usize,
 ) -> Vec<(&'a str, f32)> {
-    // (1)
-    let query_id = if let Some(id) = word_to_id.0.get(query) {
-        id
-    } else {
-        return Vec::new();
+    // (1) fall back to the closest typo-tolerant derivation when the query
+    // itself isn't in the vocabulary, instead of silently returning nothing.
+    let query_id = match word_to_id.0.get(query) {
+        Some(&id) => id,
+        None => match find_word_derivations(query, word_to_id).first() {
+            Some(&(closest, _)) => word_to_id.0[closest],
+            None => return Vec::new(),
+        },
     };
 
     let query_vec = &word_matrix.0[query_id.0];
 
-    // (2)
-    let mut similarity = word_matrix
-        .0
-        .iter()
-        .enumerate()
-        .filter(|t| query_id.0 != t.0)
-        .map(|(i, v)| {
-            (
-                id_to_word.0[&WordId(i)].as_str(),
-                cos_similarity(v, query_vec),
-            )
-        }).collect::<Vec<_>>();
-    similarity.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-    similarity.reverse();
+    // (2) keep only the `limit` best candidates in a bounded min-heap rather
+    // than sorting every word in the vocabulary.
+    let mut heap: BinaryHeap<Reverse<ScoredWord>> = BinaryHeap::with_capacity(limit + 1);
+    for (i, v) in word_matrix.0.iter().enumerate() {
+        if i == query_id.0 {
+            continue;
+        }
+        let score = cos_similarity(v, query_vec);
+        if score.is_nan() {
+            continue;
+        }
+        heap.push(Reverse(ScoredWord {
+            score,
+            word: id_to_word.0[&WordId(i)].as_str(),
+        }));
+        if heap.len() > limit {
+            heap.pop();
+        }
+    }
+
+    let mut similarity = heap
+        .into_iter()
+        .map(|Reverse(s)| (s.word, s.score))
+        .collect::<Vec<_>>();
+    similarity.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.0.cmp(b.0))
+    });
     similarity
 }
 
@@ -119,6 +233,530 @@ pub fn ppmi(c: &CoMatrix) -> PmiMatrix {
     PmiMatrix(m)
 }
 
+pub fn cos_similarity_f32(x: &[f32], y: &[f32]) -> f32 {
+    let x0 = x.iter().map(|&x| x * x).sum::<f32>().sqrt() + EPSILON;
+    let y0 = y.iter().map(|&y| y * y).sum::<f32>().sqrt() + EPSILON;
+    x.iter()
+        .zip(y.iter())
+        .map(|(&x, &y)| (x / x0) * (y / y0))
+        .sum()
+}
+
+/// A word-vector matrix that can hand back a dense `f32` row for a given
+/// word, regardless of whether the underlying counts are `usize` (`CoMatrix`)
+/// or already-weighted `f32` (`PmiMatrix`).
+pub trait VectorMatrix {
+    fn vocab_size(&self) -> usize;
+    fn row_f32(&self, id: WordId) -> Vec<f32>;
+}
+
+impl VectorMatrix for CoMatrix {
+    fn vocab_size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn row_f32(&self, id: WordId) -> Vec<f32> {
+        self.0[id.0].iter().map(|&v| v as f32).collect()
+    }
+}
+
+impl VectorMatrix for PmiMatrix {
+    fn vocab_size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn row_f32(&self, id: WordId) -> Vec<f32> {
+        self.0[id.0].clone()
+    }
+}
+
+// "a is to b as c is to ?": vec(b) - vec(a) + vec(c), ranked by cosine
+// similarity against every vocabulary row except a, b and c themselves.
+pub fn analogy<'a, M: VectorMatrix>(
+    a: &str,
+    b: &str,
+    c: &str,
+    word_to_id: &WordToId,
+    id_to_word: &'a IdToWord,
+    matrix: &M,
+    top_n: usize,
+) -> Vec<(&'a str, f32)> {
+    let (a_id, b_id, c_id) = match (
+        word_to_id.0.get(a),
+        word_to_id.0.get(b),
+        word_to_id.0.get(c),
+    ) {
+        (Some(&a), Some(&b), Some(&c)) => (a, b, c),
+        _ => return Vec::new(),
+    };
+
+    let a_vec = matrix.row_f32(a_id);
+    let b_vec = matrix.row_f32(b_id);
+    let c_vec = matrix.row_f32(c_id);
+    let target = b_vec
+        .iter()
+        .zip(a_vec.iter())
+        .zip(c_vec.iter())
+        .map(|((&b, &a), &c)| b - a + c)
+        .collect::<Vec<_>>();
+
+    let mut similarity = (0..matrix.vocab_size())
+        .filter(|&i| i != a_id.0 && i != b_id.0 && i != c_id.0)
+        .filter_map(|i| {
+            let score = cos_similarity_f32(&matrix.row_f32(WordId(i)), &target);
+            if score.is_nan() {
+                return None;
+            }
+            Some(ScoredWord {
+                score,
+                word: id_to_word.0[&WordId(i)].as_str(),
+            })
+        })
+        .collect::<Vec<_>>();
+    similarity.sort_by(|x, y| {
+        y.score
+            .partial_cmp(&x.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| x.word.cmp(y.word))
+    });
+    similarity.truncate(top_n);
+    similarity.into_iter().map(|s| (s.word, s.score)).collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DenseEmbedding(pub Vec<Vec<f32>>);
+
+impl VectorMatrix for DenseEmbedding {
+    fn vocab_size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn row_f32(&self, id: WordId) -> Vec<f32> {
+        self.0[id.0].clone()
+    }
+}
+
+// A small, self-contained xorshift64 PRNG shared by the randomized SVD
+// (`svd` module) and the SGNS trainer below, so neither needs a `rand`
+// dependency.
+pub(crate) struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Xorshift64 {
+            state: seed.max(1),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) as f32
+    }
+
+    // Box-Muller transform: turns two uniform draws into one standard normal.
+    pub(crate) fn next_gaussian(&mut self) -> f32 {
+        let u1 = self.next_f32().max(f32::EPSILON);
+        let u2 = self.next_f32();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+    }
+}
+
+pub fn most_similar_dense<'a, M: VectorMatrix>(
+    query: &str,
+    word_to_id: &WordToId,
+    id_to_word: &'a IdToWord,
+    matrix: &M,
+) -> Vec<(&'a str, f32)> {
+    let query_id = if let Some(&id) = word_to_id.0.get(query) {
+        id
+    } else {
+        return Vec::new();
+    };
+
+    let query_vec = matrix.row_f32(query_id);
+    let mut similarity = (0..matrix.vocab_size())
+        .filter(|&i| i != query_id.0)
+        .filter_map(|i| {
+            let score = cos_similarity_f32(&matrix.row_f32(WordId(i)), &query_vec);
+            if score.is_nan() {
+                return None;
+            }
+            Some(ScoredWord {
+                score,
+                word: id_to_word.0[&WordId(i)].as_str(),
+            })
+        })
+        .collect::<Vec<_>>();
+    similarity.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.word.cmp(b.word))
+    });
+    similarity.into_iter().map(|s| (s.word, s.score)).collect()
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SgnsConfig {
+    pub dim: usize,
+    pub window_size: usize,
+    pub negative_samples: usize,
+    pub learning_rate: f32,
+    pub epochs: usize,
+    pub subsample_threshold: Option<f32>,
+}
+
+impl Default for SgnsConfig {
+    fn default() -> Self {
+        SgnsConfig {
+            dim: 100,
+            window_size: 5,
+            negative_samples: 5,
+            learning_rate: 0.025,
+            epochs: 5,
+            subsample_threshold: None,
+        }
+    }
+}
+
+// Draws words with probability proportional to count^0.75, the smoothing
+// word2vec uses so rare words are sampled more often than raw frequency
+// would suggest.
+struct UnigramTable {
+    cumulative: Vec<f32>,
+}
+
+impl UnigramTable {
+    fn new(corpus: &Corpus, vocab_size: usize) -> Self {
+        let mut counts = vec![0usize; vocab_size];
+        for word in &corpus.0 {
+            counts[word.0] += 1;
+        }
+        let weights = counts
+            .iter()
+            .map(|&c| (c as f32).powf(0.75))
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f32>();
+
+        let mut cumulative = Vec::with_capacity(vocab_size);
+        let mut acc = 0.0;
+        for w in weights {
+            acc += w / total;
+            cumulative.push(acc);
+        }
+        UnigramTable { cumulative }
+    }
+
+    fn sample(&self, rng: &mut Xorshift64) -> WordId {
+        let r = rng.next_f32();
+        let idx = self
+            .cumulative
+            .binary_search_by(|probe| probe.partial_cmp(&r).unwrap())
+            .unwrap_or_else(|i| i);
+        WordId(idx.min(self.cumulative.len() - 1))
+    }
+}
+
+// Applies one SGD step for the pair (center, other) with the given label
+// (1.0 for the true context word, 0.0 for a negative sample), accumulating
+// the resulting `w_in[center]` gradient into `error` so the caller can apply
+// it once after all of a context word's negatives have been processed.
+fn sgns_update_pair(
+    w_in: &[Vec<f32>],
+    w_out: &mut [Vec<f32>],
+    center: usize,
+    other: usize,
+    label: f32,
+    learning_rate: f32,
+    error: &mut [f32],
+) {
+    let dot = w_in[center]
+        .iter()
+        .zip(w_out[other].iter())
+        .map(|(&a, &b)| a * b)
+        .sum::<f32>();
+    let grad = learning_rate * (label - sigmoid(dot));
+    for d in 0..error.len() {
+        error[d] += grad * w_out[other][d];
+        w_out[other][d] += grad * w_in[center][d];
+    }
+}
+
+// Learns dense word vectors directly from a `Corpus` via skip-gram with
+// negative sampling, the neural counterpart to the count-based
+// co-occurrence/PPMI pipeline above. Returns `w_in`, ready to feed into
+// `most_similar_dense`/`analogy` via `DenseEmbedding`.
+pub fn train_sgns(corpus: &Corpus, vocab_size: usize, config: SgnsConfig) -> DenseEmbedding {
+    let mut rng = Xorshift64::new(0xc0ff_ee12_3456_789a);
+    let init_scale = 0.5 / config.dim as f32;
+    let mut w_in = (0..vocab_size)
+        .map(|_| {
+            (0..config.dim)
+                .map(|_| (rng.next_f32() - 0.5) * init_scale)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut w_out = vec![vec![0.0; config.dim]; vocab_size];
+
+    let table = UnigramTable::new(corpus, vocab_size);
+    let word_counts = {
+        let mut counts = vec![0usize; vocab_size];
+        for word in &corpus.0 {
+            counts[word.0] += 1;
+        }
+        counts
+    };
+    let total_words = corpus.0.len().max(1) as f32;
+
+    let mut error = vec![0.0; config.dim];
+    for _ in 0..config.epochs {
+        for center_idx in 0..corpus.0.len() {
+            let center = corpus.0[center_idx].0;
+
+            if let Some(threshold) = config.subsample_threshold {
+                let freq = word_counts[center] as f32 / total_words;
+                let keep_prob = (threshold / freq).sqrt();
+                if keep_prob < 1.0 && rng.next_f32() > keep_prob {
+                    continue;
+                }
+            }
+
+            for offset in 1..=config.window_size {
+                let mut context_positions = Vec::with_capacity(2);
+                if let Some(left) = center_idx.checked_sub(offset) {
+                    context_positions.push(left);
+                }
+                let right = center_idx + offset;
+                if right < corpus.0.len() {
+                    context_positions.push(right);
+                }
+
+                for ctx_pos in context_positions {
+                    let ctx = corpus.0[ctx_pos].0;
+
+                    error.iter_mut().for_each(|e| *e = 0.0);
+                    sgns_update_pair(&w_in, &mut w_out, center, ctx, 1.0, config.learning_rate, &mut error);
+                    for _ in 0..config.negative_samples {
+                        let neg = table.sample(&mut rng).0;
+                        if neg == ctx {
+                            continue;
+                        }
+                        sgns_update_pair(&w_in, &mut w_out, center, neg, 0.0, config.learning_rate, &mut error);
+                    }
+
+                    for d in 0..config.dim {
+                        w_in[center][d] += error[d];
+                    }
+                }
+            }
+        }
+    }
+
+    DenseEmbedding(w_in)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Word2VecError {
+    InvalidHeader,
+    VocabSizeMismatch { expected: usize, actual: usize },
+    DimensionMismatch { word: String, expected: usize, actual: usize },
+    TrailingBytes { extra: usize },
+}
+
+impl fmt::Display for Word2VecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Word2VecError::InvalidHeader => write!(f, "invalid word2vec header or body"),
+            Word2VecError::VocabSizeMismatch { expected, actual } => write!(
+                f,
+                "vocab_size mismatch: header declared {}, but found {} words",
+                expected, actual
+            ),
+            Word2VecError::DimensionMismatch {
+                word,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "word {:?} has {} values, expected {}",
+                word, actual, expected
+            ),
+            Word2VecError::TrailingBytes { extra } => write!(
+                f,
+                "{} trailing byte(s) after the last vector",
+                extra
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Word2VecError {}
+
+// Writes the common word2vec text format: a "<vocab_size> <dim>" header
+// followed by one "<word> f0 f1 ... f_{dim-1}" line per word, in id order.
+pub fn write_word2vec_text(id_to_word: &IdToWord, vectors: &[Vec<f32>]) -> String {
+    let vocab_size = vectors.len();
+    let dim = vectors.first().map_or(0, |v| v.len());
+    let mut out = format!("{} {}\n", vocab_size, dim);
+    for (i, vector) in vectors.iter().enumerate() {
+        out.push_str(&id_to_word.0[&WordId(i)]);
+        for value in vector {
+            out.push(' ');
+            out.push_str(&value.to_string());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn read_word2vec_text(
+    text: &str,
+) -> Result<(WordToId, IdToWord, Vec<Vec<f32>>), Word2VecError> {
+    let mut lines = text.lines();
+    let header = lines.next().ok_or(Word2VecError::InvalidHeader)?;
+    let mut header_fields = header.split_whitespace();
+    let vocab_size: usize = header_fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or(Word2VecError::InvalidHeader)?;
+    let dim: usize = header_fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or(Word2VecError::InvalidHeader)?;
+
+    let mut word_to_id = WordToId::default();
+    let mut id_to_word = IdToWord::default();
+    let mut vectors = Vec::with_capacity(vocab_size);
+
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let word = fields.next().ok_or(Word2VecError::InvalidHeader)?.to_owned();
+        let vector = fields
+            .map(|f| f.parse::<f32>().map_err(|_| Word2VecError::InvalidHeader))
+            .collect::<Result<Vec<_>, _>>()?;
+        if vector.len() != dim {
+            return Err(Word2VecError::DimensionMismatch {
+                word,
+                expected: dim,
+                actual: vector.len(),
+            });
+        }
+        let id = WordId(id_to_word.0.len());
+        word_to_id.0.insert(word.clone(), id);
+        id_to_word.0.insert(id, word);
+        vectors.push(vector);
+    }
+
+    if vectors.len() != vocab_size {
+        return Err(Word2VecError::VocabSizeMismatch {
+            expected: vocab_size,
+            actual: vectors.len(),
+        });
+    }
+
+    Ok((word_to_id, id_to_word, vectors))
+}
+
+// Writes the word2vec binary format: an ASCII "<vocab_size> <dim>" header
+// line, then for each word its bytes, a trailing space, and `dim`
+// little-endian f32s back to back.
+pub fn write_word2vec_binary(id_to_word: &IdToWord, vectors: &[Vec<f32>]) -> Vec<u8> {
+    let vocab_size = vectors.len();
+    let dim = vectors.first().map_or(0, |v| v.len());
+    let mut out = format!("{} {}\n", vocab_size, dim).into_bytes();
+    for (i, vector) in vectors.iter().enumerate() {
+        out.extend_from_slice(id_to_word.0[&WordId(i)].as_bytes());
+        out.push(b' ');
+        for value in vector {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    out
+}
+
+pub fn read_word2vec_binary(
+    data: &[u8],
+) -> Result<(WordToId, IdToWord, Vec<Vec<f32>>), Word2VecError> {
+    let header_end = data
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or(Word2VecError::InvalidHeader)?;
+    let header =
+        std::str::from_utf8(&data[..header_end]).map_err(|_| Word2VecError::InvalidHeader)?;
+    let mut header_fields = header.split_whitespace();
+    let vocab_size: usize = header_fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or(Word2VecError::InvalidHeader)?;
+    let dim: usize = header_fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or(Word2VecError::InvalidHeader)?;
+
+    let mut word_to_id = WordToId::default();
+    let mut id_to_word = IdToWord::default();
+    let mut vectors = Vec::with_capacity(vocab_size);
+
+    let mut pos = header_end + 1;
+    for _ in 0..vocab_size {
+        let word_end = data[pos..]
+            .iter()
+            .position(|&b| b == b' ')
+            .map(|p| pos + p)
+            .ok_or(Word2VecError::InvalidHeader)?;
+        let word = std::str::from_utf8(&data[pos..word_end])
+            .map_err(|_| Word2VecError::InvalidHeader)?
+            .to_owned();
+        pos = word_end + 1;
+
+        let mut vector = Vec::with_capacity(dim);
+        for _ in 0..dim {
+            let bytes: [u8; 4] = data
+                .get(pos..pos + 4)
+                .and_then(|s| s.try_into().ok())
+                .ok_or(Word2VecError::InvalidHeader)?;
+            vector.push(f32::from_le_bytes(bytes));
+            pos += 4;
+        }
+        if data.get(pos) == Some(&b' ') || data.get(pos) == Some(&b'\n') {
+            pos += 1;
+        }
+
+        let id = WordId(id_to_word.0.len());
+        word_to_id.0.insert(word.clone(), id);
+        id_to_word.0.insert(id, word);
+        vectors.push(vector);
+    }
+
+    // The loop above always produces exactly `vocab_size` entries (it either
+    // fills the full range or bails out early via `?`), so what's left to
+    // check is that the file doesn't have trailing garbage past them.
+    if pos != data.len() {
+        return Err(Word2VecError::TrailingBytes {
+            extra: data.len() - pos,
+        });
+    }
+
+    Ok((word_to_id, id_to_word, vectors))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,16 +795,189 @@ mod tests {
         let text = "You say goodbye and I say hello.";
         let (corpus, word_to_id, id_to_word) = prerocess(text);
         let c = create_co_matrix(&corpus, 1);
-        let similarity = most_similar("you", &word_to_id, &id_to_word, &c);
+        let similarity = most_similar("you", &word_to_id, &id_to_word, &c, 5);
         assert_eq!(
             &similarity[..5],
             [
+                ("goodbye", 0.70710665),
                 ("hello", 0.70710665),
                 ("i", 0.70710665),
-                ("goodbye", 0.70710665),
                 (".", 0.0),
                 ("and", 0.0)
             ]
         );
     }
+
+    #[test]
+    fn most_similar_respects_limit() {
+        let text = "You say goodbye and I say hello.";
+        let (corpus, word_to_id, id_to_word) = prerocess(text);
+        let c = create_co_matrix(&corpus, 1);
+        let similarity = most_similar("you", &word_to_id, &id_to_word, &c, 2);
+        assert_eq!(similarity, [("goodbye", 0.70710665), ("hello", 0.70710665)]);
+    }
+
+    #[test]
+    fn find_word_derivations_works() {
+        let text = "You say goodbye and I say hello.";
+        let (corpus, word_to_id, _id_to_word) = prerocess(text);
+        let _ = corpus;
+        let derivations = find_word_derivations("goodby", &word_to_id);
+        assert_eq!(derivations[0], ("goodbye", 1));
+    }
+
+    #[test]
+    fn most_similar_resolves_typos() {
+        let text = "You say goodbye and I say hello.";
+        let (corpus, word_to_id, id_to_word) = prerocess(text);
+        let c = create_co_matrix(&corpus, 1);
+        let typo = most_similar("goodby", &word_to_id, &id_to_word, &c, 5);
+        let exact = most_similar("goodbye", &word_to_id, &id_to_word, &c, 5);
+        assert_eq!(typo, exact);
+    }
+
+    #[test]
+    fn analogy_works() {
+        let text = "You say goodbye and I say hello.";
+        let (corpus, word_to_id, id_to_word) = prerocess(text);
+        let c = create_co_matrix(&corpus, 1);
+        let result = analogy("you", "i", "say", &word_to_id, &id_to_word, &c, 3);
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn reduce_dims_works() {
+        let text = "You say goodbye and I say hello.";
+        let (corpus, word_to_id, id_to_word) = prerocess(text);
+        let c = create_co_matrix(&corpus, 1);
+        let p = ppmi(&c);
+        let dense = reduce_dims(&p, 2);
+        assert_eq!(dense.0.len(), p.0.len());
+        assert!(dense.0.iter().all(|v| v.len() == 2));
+
+        let similarity = most_similar_dense("you", &word_to_id, &id_to_word, &dense);
+        assert_eq!(similarity.len(), p.0.len() - 1);
+    }
+
+    #[test]
+    fn most_similar_dense_skips_nan_rows() {
+        let id_to_word = IdToWord(HashMap::from([
+            (WordId(0), "you".to_owned()),
+            (WordId(1), "say".to_owned()),
+            (WordId(2), "corrupt".to_owned()),
+        ]));
+        let word_to_id = WordToId(HashMap::from([
+            ("you".to_owned(), WordId(0)),
+            ("say".to_owned(), WordId(1)),
+            ("corrupt".to_owned(), WordId(2)),
+        ]));
+        let dense = DenseEmbedding(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![f32::NAN, 1.0]]);
+
+        let similarity = most_similar_dense("you", &word_to_id, &id_to_word, &dense);
+        assert_eq!(similarity, [("say", 0.0)]);
+    }
+
+    #[test]
+    fn analogy_skips_nan_rows() {
+        let id_to_word = IdToWord(HashMap::from([
+            (WordId(0), "you".to_owned()),
+            (WordId(1), "say".to_owned()),
+            (WordId(2), "hello".to_owned()),
+            (WordId(3), "corrupt".to_owned()),
+        ]));
+        let word_to_id = WordToId(HashMap::from([
+            ("you".to_owned(), WordId(0)),
+            ("say".to_owned(), WordId(1)),
+            ("hello".to_owned(), WordId(2)),
+            ("corrupt".to_owned(), WordId(3)),
+        ]));
+        let dense = DenseEmbedding(vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 1.0],
+            vec![f32::NAN, 1.0],
+        ]);
+
+        let result = analogy("you", "say", "hello", &word_to_id, &id_to_word, &dense, 5);
+        assert!(result.iter().all(|(word, _)| *word != "corrupt"));
+    }
+
+    #[test]
+    fn train_sgns_produces_dense_vectors_deterministically() {
+        let text = "You say goodbye and I say hello.";
+        let (corpus, word_to_id, _id_to_word) = prerocess(text);
+        let vocab_size = word_to_id.0.len();
+        let config = SgnsConfig {
+            dim: 4,
+            window_size: 1,
+            negative_samples: 2,
+            learning_rate: 0.05,
+            epochs: 3,
+            subsample_threshold: None,
+        };
+
+        let first = train_sgns(&corpus, vocab_size, config);
+        let second = train_sgns(&corpus, vocab_size, config);
+
+        assert_eq!(first.0.len(), vocab_size);
+        assert!(first.0.iter().all(|v| v.len() == 4));
+        assert_eq!(first.0, second.0);
+    }
+
+    #[test]
+    fn word2vec_text_roundtrip() {
+        let id_to_word = IdToWord(HashMap::from([
+            (WordId(0), "you".to_owned()),
+            (WordId(1), "say".to_owned()),
+        ]));
+        let vectors = vec![vec![0.5, -0.25], vec![1.0, 2.0]];
+
+        let text = write_word2vec_text(&id_to_word, &vectors);
+        let (word_to_id, read_id_to_word, read_vectors) = read_word2vec_text(&text).unwrap();
+        assert_eq!(read_vectors, vectors);
+        assert_eq!(read_id_to_word.0[&word_to_id.0["you"]], "you");
+        assert_eq!(read_id_to_word.0[&word_to_id.0["say"]], "say");
+    }
+
+    #[test]
+    fn word2vec_text_rejects_dimension_mismatch() {
+        let text = "2 2\nyou 0.5 -0.25\nsay 1.0\n";
+        assert_eq!(
+            read_word2vec_text(text).unwrap_err(),
+            Word2VecError::DimensionMismatch {
+                word: "say".to_owned(),
+                expected: 2,
+                actual: 1
+            }
+        );
+    }
+
+    #[test]
+    fn word2vec_binary_roundtrip() {
+        let id_to_word = IdToWord(HashMap::from([
+            (WordId(0), "you".to_owned()),
+            (WordId(1), "say".to_owned()),
+        ]));
+        let vectors = vec![vec![0.5, -0.25], vec![1.0, 2.0]];
+
+        let data = write_word2vec_binary(&id_to_word, &vectors);
+        let (word_to_id, read_id_to_word, read_vectors) = read_word2vec_binary(&data).unwrap();
+        assert_eq!(read_vectors, vectors);
+        assert_eq!(read_id_to_word.0[&word_to_id.0["you"]], "you");
+        assert_eq!(read_id_to_word.0[&word_to_id.0["say"]], "say");
+    }
+
+    #[test]
+    fn word2vec_binary_rejects_trailing_bytes() {
+        let id_to_word = IdToWord(HashMap::from([(WordId(0), "you".to_owned())]));
+        let vectors = vec![vec![0.5, -0.25]];
+
+        let mut data = write_word2vec_binary(&id_to_word, &vectors);
+        data.extend_from_slice(b"garbage");
+
+        assert_eq!(
+            read_word2vec_binary(&data).unwrap_err(),
+            Word2VecError::TrailingBytes { extra: 7 }
+        );
+    }
 }